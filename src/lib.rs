@@ -1,8 +1,17 @@
+//! `no_std` when the `std` feature (on by default) is disabled. Everything this crate needs
+//! is in `core`, so the tracker and sentinel types work unchanged in `#![no_std]` contexts;
+//! only the test suite uses `std`.
+#![cfg_attr(not(feature = "std"), no_std)]
+// The test suite spells boolean checks as `assert_eq!(true/false, ...)` throughout; keep that
+// established style instead of churning every assertion to satisfy a newer clippy lint.
+#![allow(clippy::bool_assert_comparison)]
+
 mod sentinel;
 use sentinel::*;
 
-use std::ops::{Deref, DerefMut};
-use std::fmt;
+use core::ops::{Deref, DerefMut};
+use core::fmt;
+pub use core::sync::atomic::Ordering;
 
 
 pub struct MoveMutTracker<T, K> {
@@ -15,6 +24,25 @@ pub struct MoveRelMutTracker<T, K: PartialEq + Copy> {
     sentinel: MoveRelMutSentinel<K>
 }
 
+pub struct GenMutTracker<T> {
+    value: T,
+    sentinel: GenMutSentinel
+}
+
+/// Thread-safe sibling of `MoveMutTracker`, built on `AtomicPtr` instead of `Cell` so the dirty
+/// flag can be observed and reset from other threads. The `Cell`-based trackers are untouched
+/// and stay zero-overhead; reach for this one specifically when a tracked value is shared
+/// across threads.
+pub struct AtomicMoveMutTracker<T, K> {
+    value: T,
+    sentinel: AtomicMoveMutSentinel<K>
+}
+
+/// Opaque snapshot of a `GenMutTracker`'s generation, captured with `GenMutTracker::generation`
+/// and later replayed through `GenMutTracker::changed_since`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Gen(u64);
+
 impl<T, K> MoveMutTracker<T, K> {
     #[inline(always)]
     pub fn new(value: T) -> MoveMutTracker<T, K> {
@@ -63,6 +91,125 @@ impl<T, K: PartialEq + Copy> MoveRelMutTracker<T, K> {
     }
 }
 
+impl<T> GenMutTracker<T> {
+    #[inline(always)]
+    pub fn new(value: T) -> GenMutTracker<T> {
+        GenMutTracker {
+            value,
+            sentinel: GenMutSentinel::new()
+        }
+    }
+
+    /// Snapshots the tracker's current generation, bumping it first if the value was relocated
+    /// since the last check.
+    #[inline(always)]
+    pub fn generation(this: &Self) -> Gen {
+        Gen(this.sentinel.generation())
+    }
+
+    #[inline(always)]
+    pub fn changed_since(this: &Self, snapshot: Gen) -> bool {
+        Self::generation(this) != snapshot
+    }
+}
+
+impl<T, K> AtomicMoveMutTracker<T, K> {
+    #[inline(always)]
+    pub fn new(value: T) -> AtomicMoveMutTracker<T, K> {
+        AtomicMoveMutTracker {
+            value,
+            sentinel: AtomicMoveMutSentinel::mutated()
+        }
+    }
+
+    #[inline(always)]
+    pub fn was_moved_or_mutated(this: &Self) -> bool {
+        this.sentinel.was_moved_or_mutated()
+    }
+
+    #[inline(always)]
+    pub fn was_moved_or_mutated_with_ordering(this: &Self, ordering: Ordering) -> bool {
+        this.sentinel.was_moved_or_mutated_with_ordering(ordering)
+    }
+
+    #[inline(always)]
+    pub fn set_unmutated(this: &Self, _key: K) {
+        this.sentinel.set_unmutated(_key);
+    }
+
+    #[inline(always)]
+    pub fn set_unmutated_with_ordering(this: &Self, _key: K, ordering: Ordering) {
+        this.sentinel.set_unmutated_with_ordering(_key, ordering);
+    }
+}
+
+/// Common interface over `MoveMutTracker` and `MoveRelMutTracker`, so generic code can check
+/// and clear a tracker's dirty flag without caring which flavor it's holding.
+pub trait MutTracked {
+    type Key;
+
+    fn was_moved_or_mutated(&self, key: &Self::Key) -> bool;
+    fn set_unmutated(&self, key: &Self::Key);
+}
+
+impl<T, K> MutTracked for MoveMutTracker<T, K> {
+    // `MoveMutTracker`'s key is never actually read by the sentinel, so there's nothing
+    // meaningful to thread through `MutTracked`; callers just pass `&()`.
+    type Key = ();
+
+    #[inline(always)]
+    fn was_moved_or_mutated(&self, _key: &()) -> bool {
+        MoveMutTracker::was_moved_or_mutated(self)
+    }
+
+    #[inline(always)]
+    fn set_unmutated(&self, _key: &()) {
+        self.sentinel.mark_unmutated();
+    }
+}
+
+impl<T, K: PartialEq + Copy> MutTracked for MoveRelMutTracker<T, K> {
+    type Key = K;
+
+    #[inline(always)]
+    fn was_moved_or_mutated(&self, key: &K) -> bool {
+        MoveRelMutTracker::was_moved_or_mutated(self, key)
+    }
+
+    #[inline(always)]
+    fn set_unmutated(&self, key: &K) {
+        MoveRelMutTracker::set_unmutated(self, key)
+    }
+}
+
+/// Wraps `Self` in a tracker uniformly, without the caller picking a tracker flavor by hand.
+///
+/// Implemented per-type with `impl_trackable!`, not as a blanket `impl<T> Trackable for T` --
+/// a blanket impl would pin every type's `Tracker` to `MoveMutTracker<T, ()>` and rule out ever
+/// giving a specific type a more fitting impl later (e.g. a `MoveRelMutTracker` for a type that
+/// already carries its own key).
+pub trait Trackable {
+    type Tracker;
+
+    fn into_tracker(self) -> Self::Tracker;
+}
+
+/// Opts `$ty` into `Trackable`, wrapping it in `MoveMutTracker<$ty, ()>` -- the default tracker
+/// flavor for types with no relative key of their own.
+#[macro_export]
+macro_rules! impl_trackable {
+    ($ty:ty) => {
+        impl $crate::Trackable for $ty {
+            type Tracker = $crate::MoveMutTracker<$ty, ()>;
+
+            #[inline(always)]
+            fn into_tracker(self) -> Self::Tracker {
+                $crate::MoveMutTracker::new(self)
+            }
+        }
+    };
+}
+
 macro_rules! impl_deref {
     ($tracker:ident$({K: $($t:tt)+})*) => {
         impl<T, K $(: $($t)+)*> Deref for $tracker<T, K> {
@@ -87,6 +234,25 @@ macro_rules! impl_deref {
 
 impl_deref!(MoveMutTracker);
 impl_deref!(MoveRelMutTracker{K: PartialEq + Copy});
+impl_deref!(AtomicMoveMutTracker);
+
+// `GenMutTracker` has no `K` type parameter, so it falls outside what `impl_deref!` expects.
+impl<T> Deref for GenMutTracker<T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T> DerefMut for GenMutTracker<T> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut T {
+        self.sentinel.bump();
+        &mut self.value
+    }
+}
 
 impl<T, K> From<T> for MoveMutTracker<T, K> {
     #[inline(always)]
@@ -95,6 +261,20 @@ impl<T, K> From<T> for MoveMutTracker<T, K> {
     }
 }
 
+impl<T> From<T> for GenMutTracker<T> {
+    #[inline(always)]
+    fn from(t: T) -> GenMutTracker<T> {
+        GenMutTracker::new(t)
+    }
+}
+
+impl<T, K> From<T> for AtomicMoveMutTracker<T, K> {
+    #[inline(always)]
+    fn from(t: T) -> AtomicMoveMutTracker<T, K> {
+        AtomicMoveMutTracker::new(t)
+    }
+}
+
 impl<T: Clone, K> Clone for MoveMutTracker<T, K> {
     #[inline(always)]
     fn clone(&self) -> MoveMutTracker<T, K> {
@@ -144,7 +324,59 @@ impl<T: fmt::Debug, K: PartialEq + Copy> fmt::Debug for MoveRelMutTracker<T, K>
     }
 }
 
-#[cfg(test)]
+impl<T: Clone> Clone for GenMutTracker<T> {
+    #[inline(always)]
+    fn clone(&self) -> GenMutTracker<T> {
+        GenMutTracker {
+            value: self.value.clone(),
+            sentinel: self.sentinel.clone()
+        }
+    }
+
+    #[inline(always)]
+    fn clone_from(&mut self, source: &Self) {
+        self.sentinel.bump();
+        self.value.clone_from(source);
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for GenMutTracker<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("GenMutTracker")
+            .field("sentinel", &self.sentinel)
+            .field("value", &self.value)
+            .finish()
+    }
+}
+
+impl<T: Clone, K> Clone for AtomicMoveMutTracker<T, K> {
+    #[inline(always)]
+    fn clone(&self) -> AtomicMoveMutTracker<T, K> {
+        AtomicMoveMutTracker {
+            value: self.value.clone(),
+            sentinel: self.sentinel.clone()
+        }
+    }
+
+    #[inline(always)]
+    fn clone_from(&mut self, source: &Self) {
+        self.sentinel.set_mutated();
+        self.value.clone_from(source);
+    }
+}
+
+impl<T: fmt::Debug, K> fmt::Debug for AtomicMoveMutTracker<T, K> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("AtomicMoveMutTracker")
+            .field("sentinel", &self.sentinel)
+            .field("value", &self.value)
+            .finish()
+    }
+}
+
+// Uses `Box` from the std prelude and `::std::mem::swap`, so only run under the `std` feature --
+// a `no_std` build has nothing to gain from a confusing compile error here instead of "0 tests".
+#[cfg(all(test, feature = "std"))]
 mod test {
     use super::*;
     struct Key;
@@ -224,4 +456,71 @@ mod test {
         *container.tracker = 1;
         assert_eq!(true, MoveRelMutTracker::was_moved_or_mutated(&container.tracker, &container.key));
     }
+
+    #[test]
+    fn generation() {
+        let mut tracker = GenMutTracker::new(0);
+        let gen0 = GenMutTracker::generation(&tracker);
+        assert_eq!(false, GenMutTracker::changed_since(&tracker, gen0));
+
+        *tracker = 1;
+        assert_eq!(true, GenMutTracker::changed_since(&tracker, gen0));
+        let gen1 = GenMutTracker::generation(&tracker);
+        assert_eq!(false, GenMutTracker::changed_since(&tracker, gen1));
+
+        let tracker_on_heap = Box::new(tracker);
+        assert_eq!(true, GenMutTracker::changed_since(&tracker_on_heap, gen1));
+    }
+
+    #[test]
+    fn atomic_moved() {
+        let tracker: AtomicMoveMutTracker<_, Key> = AtomicMoveMutTracker::new(0);
+        assert_eq!(true, AtomicMoveMutTracker::was_moved_or_mutated(&tracker));
+
+        AtomicMoveMutTracker::set_unmutated(&tracker, Key);
+        assert_eq!(false, AtomicMoveMutTracker::was_moved_or_mutated(&tracker));
+
+        let tracker_on_heap = Box::new(tracker);
+        assert_eq!(true, AtomicMoveMutTracker::was_moved_or_mutated(&tracker_on_heap));
+    }
+
+    #[test]
+    fn atomic_mutated() {
+        let mut tracker: AtomicMoveMutTracker<_, Key> = AtomicMoveMutTracker::new(0);
+        assert_eq!(true, AtomicMoveMutTracker::was_moved_or_mutated(&tracker));
+
+        AtomicMoveMutTracker::set_unmutated_with_ordering(&tracker, Key, Ordering::SeqCst);
+        assert_eq!(false, AtomicMoveMutTracker::was_moved_or_mutated_with_ordering(&tracker, Ordering::SeqCst));
+
+        *tracker = 1;
+        assert_eq!(true, AtomicMoveMutTracker::was_moved_or_mutated(&tracker));
+    }
+
+    fn check_mut_tracked<M: MutTracked>(m: &M, key: &M::Key) -> bool {
+        MutTracked::was_moved_or_mutated(m, key)
+    }
+
+    #[test]
+    fn mut_tracked_generic() {
+        let tracker: MoveMutTracker<_, Key> = MoveMutTracker::new(0);
+        assert_eq!(true, check_mut_tracked(&tracker, &()));
+        MutTracked::set_unmutated(&tracker, &());
+        assert_eq!(false, check_mut_tracked(&tracker, &()));
+
+        let container = Container {
+            key: KeyInt(42),
+            tracker: MoveRelMutTracker::new(1)
+        };
+        assert_eq!(true, check_mut_tracked(&container.tracker, &container.key));
+        MutTracked::set_unmutated(&container.tracker, &container.key);
+        assert_eq!(false, check_mut_tracked(&container.tracker, &container.key));
+    }
+
+    impl_trackable!(u32);
+
+    #[test]
+    fn trackable_into_tracker() {
+        let tracker = 0u32.into_tracker();
+        assert_eq!(true, MoveMutTracker::was_moved_or_mutated(&tracker));
+    }
 }
@@ -1,8 +1,10 @@
-use std::ptr::NonNull;
-use std::cell::Cell;
-use std::marker::PhantomData;
-use std::{fmt, mem};
-use std::num::NonZeroUsize;
+use core::ptr::{self, NonNull};
+use core::cell::Cell;
+use core::marker::PhantomData;
+use core::fmt;
+use core::mem::MaybeUninit;
+use core::num::NonZeroUsize;
+use core::sync::atomic::{AtomicPtr, Ordering};
 
 pub struct MoveMutSentinel<K> {
     self_ptr: Cell<NonNull<MoveMutSentinel<K>>>,
@@ -14,7 +16,20 @@ pub struct MoveMutSentinel<K> {
 #[derive(Clone)]
 pub struct MoveRelMutSentinel<K: PartialEq + Copy> {
     anchor_key_offset: Cell<NonZeroUsize>,
-    key: Cell<K>
+    key: Cell<MaybeUninit<K>>
+}
+
+pub struct GenMutSentinel {
+    self_ptr: Cell<NonNull<GenMutSentinel>>,
+    generation: Cell<u64>
+}
+
+// Same self-pointer trick as `MoveMutSentinel`, but through an `AtomicPtr` so the dirty flag
+// can be read and reset from other threads. Null stands in for "mutated" (and the initial
+// state) since `self` is never actually null; a move leaves a stale non-null pointer.
+pub struct AtomicMoveMutSentinel<K> {
+    self_ptr: AtomicPtr<AtomicMoveMutSentinel<K>>,
+    _key: PhantomData<K>
 }
 
 
@@ -29,7 +44,7 @@ impl<K> MoveMutSentinel<K> {
 
     #[inline(always)]
     pub fn was_moved_or_mutated(&self) -> bool {
-        self.self_ptr.get().as_ptr() as *const MoveMutSentinel<K> != self as *const MoveMutSentinel<K>
+        !ptr::eq(self.self_ptr.get().as_ptr(), self)
     }
 
     #[inline(always)]
@@ -39,6 +54,14 @@ impl<K> MoveMutSentinel<K> {
 
     #[inline(always)]
     pub fn set_unmutated(&self, _key: K) {
+        self.mark_unmutated();
+    }
+
+    /// Same effect as `set_unmutated`, without requiring a `K` to be on hand. `MoveMutSentinel`
+    /// never actually reads the key it's given, so callers that only have a `Key = ()`
+    /// accessor (e.g. the `MutTracked` impl) can flip the flag directly.
+    #[inline(always)]
+    pub(crate) fn mark_unmutated(&self) {
         self.self_ptr.set(unsafe{ NonNull::new_unchecked(self as *const Self as *mut Self) })
     }
 
@@ -67,7 +90,7 @@ impl<K: PartialEq + Copy> MoveRelMutSentinel<K> {
     pub fn mutated() -> MoveRelMutSentinel<K> {
         let s = MoveRelMutSentinel {
             anchor_key_offset: Cell::new(NonZeroUsize::new(1).unwrap()),
-            key: Cell::new(unsafe{ mem::uninitialized() })
+            key: Cell::new(MaybeUninit::uninit())
         };
         let offset = s.self_offset();
         s.anchor_key_offset.set(offset);
@@ -79,8 +102,9 @@ impl<K: PartialEq + Copy> MoveRelMutSentinel<K> {
         if self.anchor_key_offset.get() != self.offset_of(key) {
             true
         } else {
-            // Placed in if/else to avoid reading uninitialized memory.
-            self.key.get() != *key
+            // `anchor_key_offset` only ever equals `offset_of(key)` after `set_unmutated` has
+            // written `key`, so the slot is guaranteed initialized here.
+            unsafe{ self.key.get().assume_init() != *key }
         }
     }
 
@@ -91,11 +115,45 @@ impl<K: PartialEq + Copy> MoveRelMutSentinel<K> {
 
     #[inline(always)]
     pub fn set_unmutated(&self, key: &K) {
-        self.key.set(*key);
+        self.key.set(MaybeUninit::new(*key));
         self.anchor_key_offset.set(self.offset_of(key));
     }
 }
 
+impl GenMutSentinel {
+    #[inline(always)]
+    pub fn new() -> GenMutSentinel {
+        GenMutSentinel {
+            self_ptr: Cell::new(NonNull::dangling()),
+            generation: Cell::new(0)
+        }
+    }
+
+    // Folds move detection into the generation counter: if the sentinel's address has drifted
+    // from the last-observed `self_ptr`, the value was relocated since the last check, so bump
+    // the generation once and re-anchor before reporting it.
+    #[inline(always)]
+    fn reconcile_move(&self) {
+        let self_ptr = self as *const Self as *mut Self;
+        if !ptr::eq(self.self_ptr.get().as_ptr(), self_ptr) {
+            self.generation.set(self.generation.get().wrapping_add(1));
+            self.self_ptr.set(unsafe{ NonNull::new_unchecked(self_ptr) });
+        }
+    }
+
+    #[inline(always)]
+    pub fn bump(&self) {
+        self.reconcile_move();
+        self.generation.set(self.generation.get().wrapping_add(1));
+    }
+
+    #[inline(always)]
+    pub fn generation(&self) -> u64 {
+        self.reconcile_move();
+        self.generation.get()
+    }
+}
+
 impl<K> Clone for MoveMutSentinel<K> {
     #[inline(always)]
     fn clone(&self) -> MoveMutSentinel<K> {
@@ -114,3 +172,78 @@ impl<K> fmt::Debug for MoveMutSentinel<K> {
         }
     }
 }
+
+impl Clone for GenMutSentinel {
+    #[inline(always)]
+    fn clone(&self) -> GenMutSentinel {
+        GenMutSentinel {
+            self_ptr: self.self_ptr.clone(),
+            generation: self.generation.clone()
+        }
+    }
+}
+
+impl fmt::Debug for GenMutSentinel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Gen({})", self.generation())
+    }
+}
+
+impl<K> AtomicMoveMutSentinel<K> {
+    #[inline(always)]
+    pub fn mutated() -> AtomicMoveMutSentinel<K> {
+        AtomicMoveMutSentinel {
+            self_ptr: AtomicPtr::new(ptr::null_mut()),
+            _key: PhantomData
+        }
+    }
+
+    #[inline(always)]
+    pub fn was_moved_or_mutated(&self) -> bool {
+        self.was_moved_or_mutated_with_ordering(Ordering::Relaxed)
+    }
+
+    #[inline(always)]
+    pub fn was_moved_or_mutated_with_ordering(&self, ordering: Ordering) -> bool {
+        !ptr::eq(self.self_ptr.load(ordering), self)
+    }
+
+    #[inline(always)]
+    pub fn set_mutated(&self) {
+        self.set_mutated_with_ordering(Ordering::Relaxed);
+    }
+
+    #[inline(always)]
+    pub fn set_mutated_with_ordering(&self, ordering: Ordering) {
+        self.self_ptr.store(ptr::null_mut(), ordering);
+    }
+
+    #[inline(always)]
+    pub fn set_unmutated(&self, _key: K) {
+        self.set_unmutated_with_ordering(_key, Ordering::Relaxed);
+    }
+
+    #[inline(always)]
+    pub fn set_unmutated_with_ordering(&self, _key: K, ordering: Ordering) {
+        self.self_ptr.store(self as *const Self as *mut Self, ordering);
+    }
+}
+
+impl<K> Clone for AtomicMoveMutSentinel<K> {
+    #[inline(always)]
+    fn clone(&self) -> AtomicMoveMutSentinel<K> {
+        AtomicMoveMutSentinel {
+            self_ptr: AtomicPtr::new(self.self_ptr.load(Ordering::Relaxed)),
+            _key: PhantomData
+        }
+    }
+}
+
+impl<K> fmt::Debug for AtomicMoveMutSentinel<K> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.was_moved_or_mutated() {
+            true => write!(f, "MovedOrMutated"),
+            false => write!(f, "Unmutated")
+        }
+    }
+}